@@ -7,6 +7,8 @@ use std::collections::{
 };
 use reqwest;
 use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 
 #[derive(Deserialize, Debug)]
 struct Maze {
@@ -20,6 +22,49 @@ struct Maze {
     map: Vec<Vec<char>>,
 }
 
+impl Maze {
+    // build a Maze from a plain-text grid, e.g. read from a file or stdin,
+    // so the solver can be exercised on local fixtures without the live API.
+    // 'A' marks the starting position, 'B' the goal; both stay part of the
+    // map, every other character (including 'X' for walls) is passed through.
+    // every row must have the same length; panics otherwise, since the
+    // solver indexes the map as a dense rectangular grid.
+    fn from_reader(r: impl BufRead) -> Maze {
+        let mut map = Vec::new();
+        let mut starting_position = [0, 0];
+        let mut ending_position = [0, 0];
+
+        for (y, line) in r.lines().enumerate() {
+            let line = line.expect("could not read maze line");
+            let mut row = Vec::new();
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    'A' => starting_position = [x as i32, y as i32],
+                    'B' => ending_position = [x as i32, y as i32],
+                    _ => (),
+                }
+                row.push(c);
+            }
+            map.push(row);
+        }
+
+        if let Some(width) = map.first().map(|row| row.len()) {
+            assert!(
+                map.iter().all(|row| row.len() == width),
+                "maze rows must all have the same length"
+            );
+        }
+
+        Maze {
+            name: "local".to_string(),
+            maze_path: String::new(),
+            starting_position,
+            ending_position,
+            map,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct MazeResult {
     result: String,
@@ -49,6 +94,13 @@ struct RaceResult {
     certificate: String,
 }
 
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Mode {
+    Bfs,
+    Greedy,
+    AStar,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 struct Pair {
     key: i32,
@@ -76,56 +128,12 @@ impl Pair {
     }
 }
 
-#[derive(Hash, Eq, PartialEq, Clone, Debug)]
-struct Node {
-    pos: [i32; 2],
-    x: i32,
-    y: i32,
-    g: i32, // distance up to now
-    h: i32, // shortest possible additional distance
-    best_pred: usize,
-    direction: char,
-}
-
-impl Node {
-    fn new(
-        i: i32,
-        x: i32,
-        y: i32,
-    ) -> Node {
-        Node::from_pos([i % x, i / x], x, y)
-    }
-
-    fn from_pos(
-        pos: [i32; 2],
-        x: i32,
-        y: i32,
-    ) -> Node {
-        Node {
-            pos,
-            x,
-            y,
-            g: -1,
-            h: -1,
-            best_pred: 0,
-            direction: '@',
-        }
-    }
-
-    fn id(&self) -> usize {
-        (self.x * self.pos[1] + self.pos[0]) as usize
-    }
-
-    fn f(&self) -> i32 {
-        // sort parameter for heap, since we need a min heap, use a '-'
-        -(self.g as i32 + self.h as i32)
-    }
-
-    fn x(&self) -> i32 {
-        self.pos[0]
-    }
-    fn y(&self) -> i32 {
-        self.pos[1]
+fn heap_key(mode: Mode, g: i32, h: i32) -> i32 {
+    // sort parameter for heap, since we need a min heap, use a '-'
+    match mode {
+        Mode::Bfs => -g,
+        Mode::Greedy => -h,
+        Mode::AStar => -(g + h),
     }
 }
 
@@ -136,6 +144,13 @@ fn get_random_maze() -> Result<Maze, reqwest::Error> {
     Ok(maze)
 }
 
+fn get_local_maze(path: &str) -> std::io::Result<Maze> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    Ok(Maze::from_reader(reader))
+}
+
 fn send_maze_solution(path: &String, solution: &Vec<char>) -> Result<MazeResult, reqwest::Error> {
 
     let mut map = HashMap::new();
@@ -150,75 +165,406 @@ fn send_maze_solution(path: &String, solution: &Vec<char>) -> Result<MazeResult,
     Ok(response.json()?)
 }
 
-fn calculate_shortest_possible(s: [i32; 2], t: [i32; 2]) -> i32 {
-    (s[0] - t[0]).abs() + (s[1] - t[1]).abs()
+fn calculate_shortest_possible(s: [i32; 2], t: [i32; 2], scale: i32) -> i32 {
+    ((s[0] - t[0]).abs() + (s[1] - t[1]).abs()) * scale
+}
+
+// cost to step onto a tile, None for walls ('X'); anything else defaults
+// to 1, except a few terrain markers that are more expensive to cross.
+// solve_segment searches backwards from `to` and charges each tile as the
+// search reaches it, so a leg's reported cost is the sum of tile_cost over
+// every tile on its path except `to`: the tile of `from` is counted, the
+// tile of `to` is not. That offset is the same for every route between the
+// same two cells, so it does not change which route is cheapest
+fn tile_cost(c: char) -> Option<i32> {
+    match c {
+        'X' => None,
+        '~' => Some(3), // water
+        'M' => Some(2), // mud
+        _ => Some(1),
+    }
+}
+
+// smallest positive tile cost on the map, used to keep the Manhattan
+// heuristic admissible when tiles cost more than 1 to cross
+fn min_tile_cost(maze: &Maze) -> i32 {
+    maze.map.iter()
+        .flatten()
+        .filter_map(|&c| tile_cost(c))
+        .min()
+        .unwrap_or(1)
+}
+
+// a portal cell teleports to its twin (the other cell sharing its letter
+// label) for zero cost; 'A', 'B', 'X' and the terrain markers are reserved
+// and never treated as a portal label
+fn find_portals(maze: &Maze) -> HashMap<usize, usize> {
+    let x = maze.map[0].len();
+
+    let mut by_label: HashMap<char, Vec<usize>> = HashMap::new();
+    for (y, row) in maze.map.iter().enumerate() {
+        for (x2, &c) in row.iter().enumerate() {
+            if c.is_alphabetic() && c != 'A' && c != 'B' && c != 'X' && c != 'M' {
+                by_label.entry(c).or_insert_with(Vec::new).push(y * x + x2);
+            }
+        }
+    }
+
+    let mut portals = HashMap::new();
+    for positions in by_label.values() {
+        if positions.len() == 2 {
+            portals.insert(positions[0], positions[1]);
+            portals.insert(positions[1], positions[0]);
+        }
+    }
+    portals
+}
+
+// a portal on the border of the map is "outer", any other portal is "inner"
+fn is_outer_portal(cell: usize, x: i32, y: i32) -> bool {
+    let px = cell as i32 % x;
+    let py = cell as i32 / x;
+    px == 0 || py == 0 || px == x - 1 || py == y - 1
 }
 
-fn solve_maze(maze: &Maze) -> Vec<char> {
-    // use A* to find the shortest path
+// the deepest recursion level reachable through inner portals
+const MAX_RECURSION_DEPTH: i32 = 32;
+
+fn solve_maze(maze: &Maze, mode: Mode, beam_width: Option<usize>) -> Vec<char> {
+    solve_segment(maze, mode, maze.starting_position, maze.ending_position, beam_width, false)
+        .map(|(path, _cost)| path)
+        .unwrap_or_default()
+}
+
+// returns the direction sequence and its total weighted cost, or None if
+// `to` cannot reach `from` at all
+fn solve_segment(maze: &Maze, mode: Mode, from: [i32; 2], to: [i32; 2], beam_width: Option<usize>, recursive: bool) -> Option<(Vec<char>, i32)> {
+    // use the given search mode to find a path from `from` to `to`: BFS
+    // (ignores h, ordered by accumulated tile cost alone; shortest), Greedy best-first (fast, not necessarily
+    // shortest) or A* (shortest, informed). If `beam_width` is set, only
+    // the best-scoring frontier nodes are kept after each expansion,
+    // trading optimality for memory and speed on large mazes.
+    //
+    // paired portal cells teleport to one another at zero cost. If
+    // `recursive` is set, state additionally tracks a recursion depth:
+    // inner portals step one level deeper, outer portals step one level
+    // out (blocked at depth 0), and `to` is only a valid goal at depth 0.
 
     let y = maze.map.len() as i32;
     let x = maze.map[0].len() as i32;
-
-    let mut open_list = BinaryHeap::new();
-    let mut closed_list: HashSet<usize> = HashSet::new();
-    let mut nodes: HashMap<usize, Node> = HashMap::new();
+    let cells = (x * y) as usize;
+
+    let scale = min_tile_cost(maze);
+    let portals = find_portals(maze);
+    let layers = if recursive { (MAX_RECURSION_DEPTH + 1) as usize } else { 1 };
+    let states = cells * layers;
+
+    // portals are zero-cost long-range jumps, so the Manhattan distance no
+    // longer bounds the remaining cost from below: zero out h whenever the
+    // maze has any. A* then falls back to plain Dijkstra-by-cost ordering;
+    // Greedy, which orders by h alone, then has every frontier key tied
+    let heuristic_enabled = portals.is_empty();
+
+    // flat, grid-index-keyed arrays instead of a HashMap/HashSet: ids are
+    // dense (nx + ny*x, plus a depth*cells offset when recursive), so a Vec
+    // lookup avoids hashing in the hot loop
+    let mut g = vec![-1; states];
+    let mut h = vec![-1; states];
+    let mut best_pred = vec![0usize; states];
+    let mut direction = vec!['@'; states];
+    let mut closed = vec![false; states];
 
     // we will search the start from the end
     // such that we do not need to reverse the directions
-    let start = Node::from_pos(maze.ending_position, x, y);
-    let end = Node::from_pos(maze.starting_position, x, y);
-    nodes.insert(start.id(), start.clone());
+    // both start and the goal live at recursion depth 0
+    let start_idx = (to[0] + to[1] * x) as usize;
+    let end_idx = (from[0] + from[1] * x) as usize;
+
+    // the start tile is never stepped onto, so it costs nothing to be there;
+    // without this g[start_idx] is left at the -1 "unvisited" sentinel and
+    // every reported leg cost comes out one too low
+    g[start_idx] = 0;
+    h[start_idx] = if heuristic_enabled {
+        calculate_shortest_possible(from, to, scale)
+    } else {
+        0
+    };
 
-    open_list.push(Pair::new(start.f(), start.id()));
+    let mut open_list = BinaryHeap::new();
+    open_list.push(Pair::new(heap_key(mode, g[start_idx], h[start_idx]), start_idx));
 
     while !open_list.is_empty() {
         let c_idx = open_list.pop().unwrap().value;
-        if closed_list.contains(&c_idx) {
+        if closed[c_idx] {
             continue
         }
-        let current = nodes[&c_idx].clone();
 
         // if we reached the target, we are finished
-        if current.pos == end.pos {
+        if c_idx == end_idx {
             // read the path from our datastructures
             let mut path = Vec::new();
-            let mut b = current.clone();
-            while b.pos != start.pos {
-                path.push(b.direction);
-                b = nodes[&b.best_pred].clone();
+            let mut b = c_idx;
+            while b != start_idx {
+                // portal hops are zero-length teleports, not a real direction
+                if direction[b] != 'P' {
+                    path.push(direction[b]);
+                }
+                b = best_pred[b];
             }
-            return path
+            return Some((path, g[c_idx]))
         }
-        closed_list.insert(c_idx);
-        for (direction, [dx, dy]) in &[('N', [0,1]), ('W', [1,0]), ('E', [-1,0]), ('S', [0,-1])] {
-            let nx = current.x()+dx;
-            let ny = current.y()+dy;
+        closed[c_idx] = true;
+
+        let cell = c_idx % cells;
+        let depth = (c_idx / cells) as i32;
+        let cx = cell as i32 % x;
+        let cy = cell as i32 / x;
+        for (dir, [dx, dy]) in &[('N', [0,1]), ('W', [1,0]), ('E', [-1,0]), ('S', [0,-1])] {
+            let nx = cx+dx;
+            let ny = cy+dy;
             // we may not step outside
             if nx >= x || nx < 0 || ny >= y || ny < 0 {
                     continue
             }
-            // we may not step on walls
-            if maze.map[ny as usize][nx as usize] == 'X' {
-                continue
-            }
-            let neighbor_idx = (nx + ny * x) as usize;
-            let neighbor = nodes.entry(neighbor_idx).or_insert_with(|| Node::new(neighbor_idx as i32, x, y));
-            if closed_list.contains(&neighbor_idx) {
+            // we may not step on walls, and other tiles may cost more than 1
+            let cost = match tile_cost(maze.map[ny as usize][nx as usize]) {
+                Some(cost) => cost,
+                None => continue,
+            };
+            let neighbor_cell = (nx + ny * x) as usize;
+            let neighbor_idx = depth as usize * cells + neighbor_cell;
+            if closed[neighbor_idx] {
                 continue
-            } else if neighbor.g > current.g+1 || neighbor.g < 0 {
-                neighbor.best_pred = current.id();
-                neighbor.direction = *direction;
-                neighbor.g = current.g+1;
-                neighbor.h = calculate_shortest_possible(end.pos, neighbor.pos);
+            } else if g[neighbor_idx] > g[c_idx]+cost || g[neighbor_idx] < 0 {
+                best_pred[neighbor_idx] = c_idx;
+                direction[neighbor_idx] = *dir;
+                g[neighbor_idx] = g[c_idx]+cost;
+                h[neighbor_idx] = if heuristic_enabled {
+                    calculate_shortest_possible(from, [nx, ny], scale)
+                } else {
+                    0
+                };
                 // we cannot update next, but the old one will directly be aborted,
                 // since it will be in the closed list
-                open_list.push(Pair::new(neighbor.f(), neighbor_idx));
+                open_list.push(Pair::new(heap_key(mode, g[neighbor_idx], h[neighbor_idx]), neighbor_idx));
+            }
+        }
+
+        // stepping onto a portal teleports to its twin for zero cost
+        if let Some(&twin_cell) = portals.get(&cell) {
+            let new_depth = if !recursive {
+                0
+            } else if is_outer_portal(cell, x, y) {
+                depth - 1
+            } else {
+                depth + 1
+            };
+            if new_depth >= 0 && new_depth <= MAX_RECURSION_DEPTH {
+                let twin_idx = new_depth as usize * cells + twin_cell;
+                if !closed[twin_idx] && (g[twin_idx] > g[c_idx] || g[twin_idx] < 0) {
+                    best_pred[twin_idx] = c_idx;
+                    direction[twin_idx] = 'P';
+                    g[twin_idx] = g[c_idx];
+                    h[twin_idx] = if heuristic_enabled {
+                        calculate_shortest_possible(from, [twin_cell as i32 % x, twin_cell as i32 / x], scale)
+                    } else {
+                        0
+                    };
+                    open_list.push(Pair::new(heap_key(mode, g[twin_idx], h[twin_idx]), twin_idx));
+                }
+            }
+        }
+
+        // beam search: keep only the best `k` frontier nodes, discard the rest
+        if let Some(k) = beam_width {
+            if open_list.len() > k {
+                let mut frontier: Vec<Pair> = std::iter::from_fn(|| open_list.pop()).collect();
+                frontier.truncate(k);
+                open_list = frontier.into_iter().collect();
             }
         }
     }
 
-    Vec::new()
+    None
+}
+
+// above this many waypoints, enumerating every ordering gets too slow and
+// we fall back to the exact Held-Karp DP instead
+const PERMUTATION_LIMIT: usize = 8;
+
+// sentinel cost for a leg between two stops that has no path at all;
+// always propagated through saturating_add so it can never look cheap
+const UNREACHABLE: i32 = i32::MAX;
+
+// solve a maze that must additionally visit every position in `waypoints`,
+// in whichever order minimizes the total weighted path cost. Returns an
+// empty path if any candidate leg between two stops is unreachable.
+fn solve_maze_with_waypoints(maze: &Maze, waypoints: &Vec<[i32; 2]>, mode: Mode, beam_width: Option<usize>, recursive: bool) -> Vec<char> {
+    let n = waypoints.len();
+    if n == 0 {
+        return solve_segment(maze, mode, maze.starting_position, maze.ending_position, beam_width, recursive)
+            .map(|(path, _cost)| path)
+            .unwrap_or_default()
+    }
+
+    // stop 0 is the start, stops 1..=n are the waypoints, stop n+1 is the end
+    let mut stops = Vec::with_capacity(n + 2);
+    stops.push(maze.starting_position);
+    stops.extend(waypoints.iter().cloned());
+    stops.push(maze.ending_position);
+
+    let m = stops.len();
+    // dist holds each leg's weighted cost (see tile_cost), not its step count; a leg
+    // with no path at all is UNREACHABLE, never 0, so the optimizer can't
+    // mistake "no path" for "the cheapest possible edge"
+    let mut dist = vec![vec![UNREACHABLE; m]; m];
+    let mut legs = vec![vec![Vec::new(); m]; m];
+    for i in 0..m {
+        for j in 0..m {
+            if i == j {
+                continue
+            }
+            if let Some((leg, cost)) = solve_segment(maze, mode, stops[i], stops[j], beam_width, recursive) {
+                dist[i][j] = cost;
+                legs[i][j] = leg;
+            }
+        }
+    }
+
+    let order = if n <= PERMUTATION_LIMIT {
+        best_waypoint_order_by_permutation(&dist, n)
+    } else {
+        best_waypoint_order_by_held_karp(&dist, n)
+    };
+    // Held-Karp returns a shorter order when no tour through every
+    // waypoint is reachable
+    if order.len() != n {
+        return Vec::new()
+    }
+
+    // bail rather than stitch together an order that relies on an
+    // unreachable leg
+    let mut legs_in_order = Vec::with_capacity(order.len() + 1);
+    let mut prev = 0;
+    for stop in &order {
+        if dist[prev][*stop] == UNREACHABLE {
+            return Vec::new()
+        }
+        legs_in_order.push((prev, *stop));
+        prev = *stop;
+    }
+    if dist[prev][m - 1] == UNREACHABLE {
+        return Vec::new()
+    }
+    legs_in_order.push((prev, m - 1));
+
+    let mut path = Vec::new();
+    for (from, to) in legs_in_order {
+        path.extend(legs[from][to].iter().cloned());
+    }
+    path
+}
+
+// all permutations of `items`, in lexical order
+fn permutations(items: &[usize]) -> Vec<Vec<usize>> {
+    if items.is_empty() {
+        return vec![Vec::new()]
+    }
+
+    let mut result = Vec::new();
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        let chosen = rest.remove(i);
+        for mut perm in permutations(&rest) {
+            perm.insert(0, chosen);
+            result.push(perm);
+        }
+    }
+    result
+}
+
+// brute-force the waypoint order by trying every permutation; stop 0 is the
+// start and stop `n+1` is the end, waypoints are stops 1..=n
+fn best_waypoint_order_by_permutation(dist: &Vec<Vec<i32>>, n: usize) -> Vec<usize> {
+    let waypoints: Vec<usize> = (1..=n).collect();
+    let end = n + 1;
+
+    let mut best_cost = i32::MAX;
+    let mut best_order = waypoints.clone();
+    for perm in permutations(&waypoints) {
+        let mut cost = dist[0][perm[0]];
+        for pair in perm.windows(2) {
+            cost = cost.saturating_add(dist[pair[0]][pair[1]]);
+        }
+        cost = cost.saturating_add(dist[*perm.last().unwrap()][end]);
+
+        if cost < best_cost {
+            best_cost = cost;
+            best_order = perm;
+        }
+    }
+    best_order
+}
+
+// exact Held-Karp DP over bitmask subsets of the waypoints (stops 1..=n):
+// dp[mask][last] = cheapest way to have visited `mask`, ending at `last`
+fn best_waypoint_order_by_held_karp(dist: &Vec<Vec<i32>>, n: usize) -> Vec<usize> {
+    let end = n + 1;
+    let full_mask = (1 << n) - 1;
+
+    let mut dp = vec![vec![i32::MAX; n]; 1 << n];
+    let mut parent = vec![vec![usize::MAX; n]; 1 << n];
+
+    for i in 0..n {
+        dp[1 << i][i] = dist[0][i + 1];
+    }
+
+    for mask in 1..=full_mask {
+        for last in 0..n {
+            if mask & (1 << last) == 0 || dp[mask][last] == i32::MAX {
+                continue
+            }
+            for next in 0..n {
+                if mask & (1 << next) != 0 {
+                    continue
+                }
+                let next_mask = mask | (1 << next);
+                let cost = dp[mask][last].saturating_add(dist[last + 1][next + 1]);
+                if cost < dp[next_mask][next] {
+                    dp[next_mask][next] = cost;
+                    parent[next_mask][next] = last;
+                }
+            }
+        }
+    }
+
+    let mut best_last = 0;
+    let mut best_cost = i32::MAX;
+    for last in 0..n {
+        if dp[full_mask][last] == i32::MAX {
+            continue
+        }
+        let cost = dp[full_mask][last].saturating_add(dist[last + 1][end]);
+        if cost < best_cost {
+            best_cost = cost;
+            best_last = last;
+        }
+    }
+
+    let mut order = Vec::with_capacity(n);
+    let mut mask = full_mask;
+    let mut last = best_last;
+    loop {
+        order.push(last + 1);
+        let prev = parent[mask][last];
+        if prev == usize::MAX {
+            break
+        }
+        mask ^= 1 << last;
+        last = prev;
+    }
+    order.reverse();
+    order
 }
 
 fn show_maze(maze: &Maze) {
@@ -308,7 +654,7 @@ fn race() -> Result<(), reqwest::Error> {
     loop {
         let maze = get_race_maze(&next)?;
         println!("{}", maze.name);
-        let solution = solve_maze(&maze);
+        let solution = solve_maze(&maze, Mode::AStar, None);
         let result = send_race_solution(&next, &solution)?;
         next = result.maze_path;
         if result.result == "finished" {
@@ -322,9 +668,76 @@ fn race() -> Result<(), reqwest::Error> {
 fn main() {
     race();
     // let maze = get_random_maze().unwrap();
-    // let solution = solve_maze(&maze);
+    // let solution = solve_maze(&maze, Mode::AStar, None);
     // send_maze_solution(&maze.maze_path, &solution);
     // println!("{:?}", solution);
     // show_maze(&maze);
     // show_maze_with_tour(&maze, &solution);
+    // let maze = get_local_maze("fixtures/maze.txt").unwrap();
+    // let solution = solve_maze(&maze, Mode::AStar, None);
+    // show_maze_with_tour(&maze, &solution);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn maze_from_str(s: &str) -> Maze {
+        Maze::from_reader(Cursor::new(s))
+    }
+
+    #[test]
+    fn solve_maze_astar_finds_shortest_path() {
+        let maze = maze_from_str("A..\n...\n..B\n");
+        let path = solve_maze(&maze, Mode::AStar, None);
+        assert_eq!(path.len(), 4); // Manhattan distance from (0,0) to (2,2)
+    }
+
+    #[test]
+    fn solve_maze_bfs_matches_astar_length_on_unit_grid() {
+        let maze = maze_from_str("A.X\n...\nX.B\n");
+        let astar = solve_maze(&maze, Mode::AStar, None);
+        let bfs = solve_maze(&maze, Mode::Bfs, None);
+        assert!(!astar.is_empty());
+        assert_eq!(astar.len(), bfs.len());
+    }
+
+    #[test]
+    fn weighted_terrain_prefers_lower_cost_route_over_fewer_steps() {
+        // the direct route crosses two water tiles (cost 3 each, total 7);
+        // the detour along the bottom row is all plain floor (5 steps, total 5)
+        let maze = maze_from_str("A~~B\n....\n");
+        let (path, cost) = solve_segment(
+            &maze, Mode::AStar, maze.starting_position, maze.ending_position, None, false,
+        ).expect("maze should be solvable");
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 5);
+    }
+
+    #[test]
+    fn unreachable_segment_returns_none_instead_of_an_empty_path() {
+        let maze = maze_from_str("A.X\nXXX\nX.B\n");
+        let result = solve_segment(
+            &maze, Mode::AStar, maze.starting_position, maze.ending_position, None, false,
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn waypoints_are_visited_in_the_cheapest_order() {
+        let maze = maze_from_str("A.....B\n");
+        let waypoints = vec![[4, 0], [2, 0]]; // deliberately out of order
+        let path = solve_maze_with_waypoints(&maze, &waypoints, Mode::AStar, None, false);
+        assert_eq!(path.len(), 6); // 2 + 2 + 2, visiting them left-to-right
+    }
+
+    #[test]
+    fn portal_teleport_shortcuts_the_path() {
+        // without the portal, the shortest route around the walls is 6 steps;
+        // stepping through the paired 'C' tiles cuts it down to 4
+        let maze = maze_from_str("A.X.C\n.....\nC.X.B\n");
+        let path = solve_maze(&maze, Mode::AStar, None);
+        assert_eq!(path.len(), 4);
+    }
 }